@@ -0,0 +1,35 @@
+/// The kind of metadata comment found in an exposition document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentType {
+    Help,
+    Type,
+    Unknown,
+}
+
+impl From<&str> for CommentType {
+    fn from(s: &str) -> Self {
+        match s {
+            "HELP" => CommentType::Help,
+            "TYPE" => CommentType::Type,
+            _ => CommentType::Unknown,
+        }
+    }
+}
+
+/// A `# HELP` or `# TYPE` line describing a metric family.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub metric: String,
+    pub comment_type: CommentType,
+    pub description: String,
+}
+
+impl Comment {
+    pub fn new(metric: String, comment_type: CommentType, description: String) -> Self {
+        Comment {
+            metric,
+            comment_type,
+            description,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crate::parser::comment::CommentType;
+use crate::parser::family::{MetricFamily, MetricType};
+use crate::parser::line_parser::{read_comment_line, read_sample_line};
+
+// Child series of a histogram/summary family carry one of these suffixes;
+// the quantile series of a summary keeps the base name and is told apart
+// only by its `quantile` label.
+const CHILD_SUFFIXES: &[&str] = &["_bucket", "_sum", "_count"];
+
+fn base_metric_name<'a>(
+    name: &'a str,
+    families: &[MetricFamily],
+    index: &HashMap<String, usize>,
+) -> &'a str {
+    for suffix in CHILD_SUFFIXES {
+        if let Some(stripped) = name.strip_suffix(suffix) {
+            if let Some(&i) = index.get(stripped) {
+                if matches!(
+                    families[i].metric_type,
+                    MetricType::Histogram | MetricType::Summary
+                ) {
+                    return stripped;
+                }
+            }
+        }
+    }
+    name
+}
+
+fn family_index(
+    name: &str,
+    families: &mut Vec<MetricFamily>,
+    index: &mut HashMap<String, usize>,
+) -> usize {
+    if let Some(&i) = index.get(name) {
+        return i;
+    }
+    let i = families.len();
+    families.push(MetricFamily::new(name.to_string()));
+    index.insert(name.to_string(), i);
+    i
+}
+
+/// Parses a full exposition document, grouping samples into [`MetricFamily`]
+/// values keyed by the metric name declared in their `# HELP`/`# TYPE`
+/// comments.
+///
+/// Blank lines and comments that aren't recognized `# HELP`/`# TYPE`
+/// declarations are skipped rather than aborting the parse. Histogram and
+/// summary child series (`_bucket`, `_sum`, `_count`, and quantile samples)
+/// are folded into their parent family once its type has been declared.
+pub fn parse_exposition(input: &str) -> Vec<MetricFamily> {
+    let mut families: Vec<MetricFamily> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for line in input.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if trimmed.starts_with('#') {
+            if let Ok((_, comment)) = read_comment_line(trimmed) {
+                let i = family_index(&comment.metric, &mut families, &mut index);
+                match comment.comment_type {
+                    CommentType::Help => families[i].help = comment.description,
+                    CommentType::Type => {
+                        families[i].metric_type = comment.description.as_str().into()
+                    }
+                    CommentType::Unknown => {}
+                }
+            }
+            continue;
+        }
+
+        if let Ok((_, sample)) = read_sample_line(trimmed) {
+            let base = base_metric_name(&sample.name, &families, &index).to_string();
+            let i = family_index(&base, &mut families, &mut index);
+            families[i].samples.push(sample);
+        }
+    }
+
+    families
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::label::LabelList;
+    use crate::parser::metric_data::SampleData;
+
+    #[test]
+    fn test_parse_exposition_simple_family() {
+        let doc = "\
+# HELP http_requests_total The total number of HTTP requests.
+# TYPE http_requests_total counter
+http_requests_total{method=\"post\",code=\"200\"} 1027 1395066363000
+http_requests_total{method=\"post\",code=\"400\"} 3 1395066363000
+";
+        let families = parse_exposition(doc);
+        assert_eq!(families.len(), 1);
+        assert_eq!(families[0].name, "http_requests_total");
+        assert_eq!(families[0].metric_type, MetricType::Counter);
+        assert_eq!(
+            families[0].help,
+            "The total number of HTTP requests."
+        );
+        assert_eq!(families[0].samples.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_exposition_groups_histogram_children() {
+        let doc = "\
+# HELP http_request_duration_seconds A histogram of the request duration.
+# TYPE http_request_duration_seconds histogram
+http_request_duration_seconds_bucket{le=\"0.05\"} 24054
+http_request_duration_seconds_bucket{le=\"+Inf\"} 144320
+http_request_duration_seconds_sum 53423
+http_request_duration_seconds_count 144320
+";
+        let families = parse_exposition(doc);
+        assert_eq!(families.len(), 1);
+        assert_eq!(families[0].name, "http_request_duration_seconds");
+        assert_eq!(families[0].metric_type, MetricType::Histogram);
+        assert_eq!(families[0].samples.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_exposition_skips_blank_lines_and_unknown_comments() {
+        let doc = "\n\
+# this is not a HELP/TYPE comment\n\
+# TYPE untyped_metric gauge\n\
+\n\
+untyped_metric 1\n";
+        let families = parse_exposition(doc);
+        assert_eq!(families.len(), 1);
+        assert_eq!(families[0].metric_type, MetricType::Gauge);
+        assert_eq!(
+            families[0].samples,
+            vec![SampleData::new(
+                "untyped_metric".into(),
+                LabelList::new(),
+                1.0,
+                None
+            )]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use nom::error::{VerboseError, VerboseErrorKind};
+use nom::Err as NomErr;
+use std::fmt;
+
+/// A parse failure, carrying enough detail to point at what went wrong
+/// instead of just collapsing to `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Byte offset into the input where the failure was reported.
+    pub offset: usize,
+    /// 1-based column corresponding to `offset`.
+    pub column: usize,
+    /// The remaining input fragment at the point of failure.
+    pub fragment: String,
+    /// Human-readable description of what was expected.
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn from_nom(input: &str, err: NomErr<VerboseError<&str>>) -> Self {
+        match err {
+            NomErr::Error(e) | NomErr::Failure(e) => Self::from_verbose(input, e),
+            NomErr::Incomplete(_) => ParseError {
+                offset: input.len(),
+                column: input.chars().count() + 1,
+                fragment: String::new(),
+                message: "unexpected end of input".to_string(),
+            },
+        }
+    }
+
+    fn from_verbose(input: &str, err: VerboseError<&str>) -> Self {
+        // Prefer the closest `context(...)` label (e.g. "quoted string",
+        // "numeric value") over the raw nom error kind it wraps, since it
+        // reads far closer to what a human would expect.
+        let (fragment, kind) = err
+            .errors
+            .iter()
+            .find(|(_, kind)| matches!(kind, VerboseErrorKind::Context(_)))
+            .or_else(|| err.errors.first())
+            .map(|(fragment, kind)| (*fragment, kind.clone()))
+            .unwrap_or(("", VerboseErrorKind::Context("valid input")));
+
+        let offset = input.len() - fragment.len();
+        let column = input[..offset].chars().count() + 1;
+
+        ParseError {
+            offset,
+            column,
+            fragment: fragment.to_string(),
+            message: describe(&kind),
+        }
+    }
+}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Context(ctx) => format!("expected {}", ctx),
+        VerboseErrorKind::Char(c) => format!("expected '{}'", c),
+        VerboseErrorKind::Nom(kind) => format!("expected {:?}", kind),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at column {} (near {:?})",
+            self.message, self.column, self.fragment
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
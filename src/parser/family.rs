@@ -0,0 +1,46 @@
+use crate::parser::metric_data::SampleData;
+
+/// The declared type of a metric family, taken from its `# TYPE` comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+    Histogram,
+    Summary,
+    #[default]
+    Untyped,
+}
+
+impl From<&str> for MetricType {
+    fn from(s: &str) -> Self {
+        match s {
+            "counter" => MetricType::Counter,
+            "gauge" => MetricType::Gauge,
+            "histogram" => MetricType::Histogram,
+            "summary" => MetricType::Summary,
+            _ => MetricType::Untyped,
+        }
+    }
+}
+
+/// All the samples sharing a base metric name, together with the metadata
+/// declared by its `# HELP`/`# TYPE` comments.
+///
+/// For histograms and summaries, this includes the `_bucket`/`_sum`/`_count`
+/// and quantile child series grouped under the family's base name.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MetricFamily {
+    pub name: String,
+    pub metric_type: MetricType,
+    pub help: String,
+    pub samples: Vec<SampleData>,
+}
+
+impl MetricFamily {
+    pub fn new(name: String) -> Self {
+        MetricFamily {
+            name,
+            ..Default::default()
+        }
+    }
+}
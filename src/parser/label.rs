@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+/// The set of labels attached to a sample, e.g. `{method="post",code="200"}`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LabelList {
+    labels: HashMap<String, String>,
+}
+
+impl LabelList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_map(labels: HashMap<String, String>) -> Self {
+        LabelList { labels }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn get_string(&self, key: &str) -> Option<&String> {
+        self.labels.get(key)
+    }
+
+    pub fn get_number(&self, key: &str) -> Option<f64> {
+        self.labels.get(key).and_then(|v| v.parse().ok())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.labels.iter()
+    }
+}
+
+impl From<Vec<(&str, String)>> for LabelList {
+    fn from(pairs: Vec<(&str, String)>) -> Self {
+        LabelList {
+            labels: pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        }
+    }
+}
@@ -2,8 +2,8 @@ use nom::{
     branch::alt,
     bytes::complete::{escaped, tag, take_while},
     character::complete::{none_of, not_line_ending, one_of, space0},
-    combinator::{map, opt},
-    error::VerboseError,
+    combinator::{cut, map, opt},
+    error::{context, VerboseError},
     multi::separated_list0,
     number::complete::double as read_double,
     sequence::{delimited, preceded, separated_pair, terminated, tuple},
@@ -12,6 +12,7 @@ use nom::{
 };
 
 use crate::parser::comment::Comment;
+use crate::parser::error::ParseError;
 use crate::parser::label::LabelList;
 use crate::parser::metric_data::SampleData;
 
@@ -22,46 +23,85 @@ fn is_metric_char(s: char) -> bool {
     s.is_alphanumeric() || s == '_' || s == ':' || s == '.'
 }
 
+// https://prometheus.io/docs/instrumenting/exposition_formats/#comments-help-text-and-type-information
+// Only `\\`, `\"` and `\n` are valid escapes in a label value, and
+// `read_quoted_string` rejects any other escape before it gets here. Should
+// one reach this function anyway, the backslash is dropped and the
+// following character is kept as-is.
+fn unescape_label_value(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('"') => out.push('"'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
 fn read_quoted_string(input: &str) -> NomRes<&str, String> {
     let normal = none_of("\\\"");
-    let escapable = one_of("\"\\'n");
+    let escapable = one_of("\"\\n");
     let escape_non_empty = escaped(normal, '\\', escapable);
-    let reduce_special_chars = |s: &str| s.replace("\\\\", "\\");
-    delimited(
-        tag("\""),
-        map(alt((escape_non_empty, tag(""))), reduce_special_chars),
-        tag("\""),
+    context(
+        "quoted string",
+        delimited(
+            tag("\""),
+            map(alt((escape_non_empty, tag(""))), unescape_label_value),
+            tag("\""),
+        ),
     )(input)
 }
 
 fn read_variable_name(input: &str) -> NomRes<&str, &str> {
-    preceded(space0, take_while(is_metric_char))(input)
+    context("metric name", preceded(space0, take_while(is_metric_char)))(input)
 }
 
 fn read_label(input: &str) -> NomRes<&str, LabelList> {
-    opt(delimited(
-        preceded(space0, tag("{")),
-        separated_list0(
-            preceded(space0, terminated(tag(","), space0)),
-            separated_pair(
-                read_variable_name,
-                preceded(space0, terminated(tag("="), space0)),
-                read_quoted_string,
+    let (input, brace) = opt(preceded(space0, tag("{")))(input)?;
+    if brace.is_none() {
+        return Ok((input, LabelList::new()));
+    }
+
+    // Once `{` is seen, this is committed to being a label list: any error
+    // in the body (bad `=`, unterminated quote, missing `}`, ...) must
+    // surface as a real parse error instead of `opt` rewinding to the
+    // brace and reporting it as "no labels present".
+    cut(terminated(
+        map(
+            separated_list0(
+                preceded(space0, terminated(tag(","), space0)),
+                separated_pair(
+                    read_variable_name,
+                    context("'='", preceded(space0, terminated(tag("="), space0))),
+                    read_quoted_string,
+                ),
             ),
+            LabelList::from,
         ),
         preceded(space0, tag("}")),
     ))(input)
-    .map(|(out, label)| (out, label.unwrap_or_default().into()))
 }
 
 fn read_value(input: &str) -> NomRes<&str, f64> {
-    preceded(
-        space0,
-        alt((
-            map(tag("+Inf"), |_| f64::INFINITY),
-            map(tag("-Inf"), |_| f64::NEG_INFINITY),
-            read_double,
-        )),
+    context(
+        "numeric value",
+        preceded(
+            space0,
+            alt((
+                map(tag("+Inf"), |_| f64::INFINITY),
+                map(tag("-Inf"), |_| f64::NEG_INFINITY),
+                read_double,
+            )),
+        ),
     )(input)
 }
 
@@ -70,9 +110,9 @@ fn read_timestamp(input: &str) -> NomRes<&str, Option<i64>> {
     opt(preceded(space0, read_timestamp_as_i64))(input)
 }
 
-fn read_comment_line(input: &str) -> NomRes<&str, Comment> {
+pub(crate) fn read_comment_line(input: &str) -> NomRes<&str, Comment> {
     let comment_identifier = tuple((tag("#"), space0));
-    let known_comment_types = alt((tag("HELP"), tag("TYPE")));
+    let known_comment_types = context("HELP or TYPE", alt((tag("HELP"), tag("TYPE"))));
 
     tuple((
         preceded(comment_identifier, known_comment_types),
@@ -85,15 +125,33 @@ fn read_comment_line(input: &str) -> NomRes<&str, Comment> {
 }
 
 // https://prometheus.io/docs/instrumenting/exposition_formats/#comments-help-text-and-type-information
-fn read_sample_line(input: &str) -> NomRes<&str, SampleData> {
+pub(crate) fn read_sample_line(input: &str) -> NomRes<&str, SampleData> {
     tuple((read_variable_name, read_label, read_value, read_timestamp))(input).map(
         |(out, (name, label, value, timestamp))| {
             (out, SampleData::new(name.into(), label, value, timestamp))
         },
     )
 }
+/// Parses a `&str` line as a sample, returning a [`ParseError`] with the
+/// column and expected construct if it fails.
+/// # Examples:
+/// ```
+/// use prometheus_wire::parser::read_sample;
+///
+/// let err = read_sample(r#"http_requests_total{method="post"}"#).unwrap_err();
+/// assert!(err.message.contains("numeric value"));
+/// ```
+pub fn read_sample(line: &str) -> Result<SampleData, ParseError> {
+    read_sample_line(line)
+        .map(|(_, sample)| sample)
+        .map_err(|e| ParseError::from_nom(line, e))
+}
+
 /// Tries to parse a `&str` line as a sample and returns [`SampleData`]
 /// containg the metric name, labels and value if it succeeds.
+///
+/// This is a thin wrapper over [`read_sample`] that discards the error
+/// details; use `read_sample` if you need to know why a line failed.
 /// # Examples:
 /// ```
 /// use prometheus_wire::parser::{SampleData, LabelList, try_read_sample};
@@ -123,23 +181,40 @@ fn read_sample_line(input: &str) -> NomRes<&str, SampleData> {
 /// assert_eq!(try_read_sample("# test"), None);
 /// ```
 pub fn try_read_sample(line: &str) -> Option<SampleData> {
-    read_sample_line(line).ok().map(|(_, metric)| metric)
+    read_sample(line).ok()
+}
+
+/// Parses a `&str` line as a comment, returning a [`ParseError`] with the
+/// column and expected construct if it fails.
+/// # Examples:
+/// ```
+/// use prometheus_wire::parser::read_comment;
+///
+/// assert!(read_comment("metric 12345").is_err());
+/// ```
+pub fn read_comment(line: &str) -> Result<Comment, ParseError> {
+    read_comment_line(line)
+        .map(|(_, comment)| comment)
+        .map_err(|e| ParseError::from_nom(line, e))
 }
 
 /// Tries to parse a `&str` line as a comment and returns [`Comment`] if it succeeds.
 ///
+/// This is a thin wrapper over [`read_comment`] that discards the error
+/// details; use `read_comment` if you need to know why a line failed.
+///
 /// # Examples:
 /// ```
 /// use prometheus_wire::parser::{Comment, CommentType, try_read_comment};
 /// assert_eq!(
 ///     try_read_comment("# HELP test1 this is a test"),
-///     Some(Comment::new(String::from("test1"), CommentType::HELP, String::from("this is a test")))
+///     Some(Comment::new(String::from("test1"), CommentType::Help, String::from("this is a test")))
 /// );
 ///
 /// assert_eq!(try_read_comment("metric 12345"), None);
 /// ```
 pub fn try_read_comment(line: &str) -> Option<Comment> {
-    read_comment_line(line).ok().map(|(_, comment)| comment)
+    read_comment(line).ok()
 }
 
 #[cfg(test)]
@@ -165,7 +240,7 @@ mod tests {
         );
         assert_eq!(
             read_quoted_string("\"new\\nline\"").unwrap(),
-            ("", "new\\nline".into())
+            ("", "new\nline".into())
         );
         assert_eq!(
             read_quoted_string("\" C:\\\\test\\\\ \"").unwrap(),
@@ -173,7 +248,7 @@ mod tests {
         );
         assert_eq!(
             read_quoted_string("\"beta:\\\"456\\\"\"").unwrap(),
-            ("", "beta:\\\"456\\\"".into())
+            ("", "beta:\"456\"".into())
         );
     }
 
@@ -197,7 +272,7 @@ mod tests {
         );
 
         let mut h2 = HashMap::new();
-        h2.insert("a_b:1".into(), "test\\\"1\\\"".into());
+        h2.insert("a_b:1".into(), "test\"1\"".into());
         h2.insert("543_a.76".into(), "C:\\test\\".into());
 
         let s = " { a_b:1 = \"test\\\"1\\\"\" , 543_a.76=\"C:\\\\test\\\\\"}";
@@ -249,7 +324,7 @@ mod tests {
         read_comment_line("# alfa").unwrap_err();
         assert_eq!(
             read_comment_line("# HELP").unwrap(),
-            ("", Comment::new("".into(), CommentType::HELP, "".into()))
+            ("", Comment::new("".into(), CommentType::Help, "".into()))
         );
         assert_eq!(
             read_comment_line("# HELP node_cpu_seconds_total Seconds the CPUs spent in each mode.")
@@ -258,7 +333,7 @@ mod tests {
                 "",
                 Comment::new(
                     "node_cpu_seconds_total".into(),
-                    CommentType::HELP,
+                    CommentType::Help,
                     "Seconds the CPUs spent in each mode.".into()
                 )
             )
@@ -269,7 +344,7 @@ mod tests {
                 "",
                 Comment::new(
                     "node_cpu_seconds_total".into(),
-                    CommentType::TYPE,
+                    CommentType::Type,
                     "counter".into()
                 )
             )
@@ -278,7 +353,7 @@ mod tests {
             read_comment_line("#    HELP     alfa").unwrap(),
             (
                 "",
-                Comment::new("alfa".into(), CommentType::HELP, "".into())
+                Comment::new("alfa".into(), CommentType::Help, "".into())
             )
         );
     }
@@ -310,7 +385,7 @@ mod tests {
         h1.insert("path".into(), "C:\\DIR\\FILE.TXT".into());
         h1.insert(
             "error".into(),
-            "Cannot find file:\\n\\\"FILE.TXT\\\"".into(),
+            "Cannot find file:\n\"FILE.TXT\"".into(),
         );
         let l = LabelList::from_map(h1);
         assert_eq!(
@@ -326,4 +401,54 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_read_quoted_string_decodes_known_escapes_only() {
+        assert_eq!(
+            read_quoted_string("\"\\\\\\\"\\n\"").unwrap(),
+            ("", "\\\"\n".into())
+        );
+        // \t isn't one of the valid escapes (\\, \" or \n), so it's rejected.
+        read_quoted_string("\"\\t\"").unwrap_err();
+    }
+
+    #[test]
+    fn test_read_sample_reports_rich_errors() {
+        let err = read_sample(r#"http_requests_total{method="post"}"#).unwrap_err();
+        assert!(err.message.contains("numeric value"));
+        assert_eq!(err.column, 35);
+
+        assert!(read_sample(r#"http_requests_total{method="post"} 1"#).is_ok());
+    }
+
+    #[test]
+    fn test_read_sample_reports_errors_inside_malformed_label_list() {
+        // A malformed label list must be reported as a label-list error,
+        // not rewound past the braces and misreported as a missing value
+        // (regression test: `opt` used to swallow these). The broken pair
+        // ends the list, so the error points at the expected `}` right
+        // after `{` (column 12), not at the bad `=` or quote itself.
+        let err = read_sample(r#"bad_metric{method="post"#).unwrap_err();
+        assert!(!err.message.contains("numeric value"));
+        assert_eq!(err.column, 12);
+
+        let err = read_sample(r#"bad_metric{method post} 1"#).unwrap_err();
+        assert!(!err.message.contains("numeric value"));
+        assert_eq!(err.column, 12);
+    }
+
+    #[test]
+    fn test_read_comment_reports_rich_errors() {
+        let err = read_comment("# alfa").unwrap_err();
+        assert!(err.message.contains("HELP") && err.message.contains("TYPE"));
+        assert_eq!(err.fragment, "alfa");
+
+        assert!(read_comment("# TYPE metric counter").is_ok());
+    }
+
+    #[test]
+    fn test_try_read_sample_and_try_read_comment_wrap_read_fns() {
+        assert_eq!(try_read_sample("# test"), None);
+        assert_eq!(try_read_comment("metric 12345"), None);
+    }
 }
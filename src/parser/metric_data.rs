@@ -0,0 +1,21 @@
+use crate::parser::label::LabelList;
+
+/// A single Prometheus sample: a metric name, its labels, value and optional timestamp.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleData {
+    pub name: String,
+    pub labels: LabelList,
+    pub value: f64,
+    pub timestamp: Option<i64>,
+}
+
+impl SampleData {
+    pub fn new(name: String, labels: LabelList, value: f64, timestamp: Option<i64>) -> Self {
+        SampleData {
+            name,
+            labels,
+            value,
+            timestamp,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+pub mod comment;
+pub mod document;
+pub mod error;
+pub mod family;
+pub mod label;
+pub mod line_parser;
+pub mod metric_data;
+pub mod stream;
+pub mod writer;
+
+pub use comment::{Comment, CommentType};
+pub use document::parse_exposition;
+pub use error::ParseError;
+pub use family::{MetricFamily, MetricType};
+pub use label::LabelList;
+pub use line_parser::{read_comment, read_sample, try_read_comment, try_read_sample};
+pub use metric_data::SampleData;
+pub use stream::{SampleStream, StreamEvent};
+pub use writer::{encode_exposition, ToExpositionString};
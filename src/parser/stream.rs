@@ -0,0 +1,157 @@
+use crate::parser::comment::Comment;
+use crate::parser::line_parser::{read_comment_line, read_sample_line};
+use crate::parser::metric_data::SampleData;
+
+/// A sample or comment produced by [`SampleStream`] as complete lines
+/// become available.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    Sample(SampleData),
+    Comment(Comment),
+}
+
+/// Incrementally parses an exposition document as it arrives in chunks,
+/// e.g. from a streamed scrape response that rarely splits on line
+/// boundaries, and can just as easily split mid-character inside a
+/// multi-byte label value.
+///
+/// Feed raw, not-necessarily-UTF-8-aligned chunks with
+/// [`feed`](SampleStream::feed); each call returns the events completed by
+/// that chunk, while any trailing partial line (including a character left
+/// split across the chunk boundary) is kept in an internal byte buffer
+/// until the next `feed` or [`finish`](SampleStream::finish).
+#[derive(Debug, Default)]
+pub struct SampleStream {
+    buffer: Vec<u8>,
+}
+
+impl SampleStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `data` to the retained buffer and parses as many complete
+    /// lines off the front of it as are available. A line that isn't valid
+    /// UTF-8 once fully assembled is skipped, same as any other malformed
+    /// line.
+    pub fn feed(&mut self, data: &[u8]) -> Vec<StreamEvent> {
+        self.buffer.extend_from_slice(data);
+        let mut events = Vec::new();
+
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            events.extend(parse_line_bytes(&line[..line.len() - 1]));
+        }
+
+        events
+    }
+
+    /// Flushes a final, unterminated line left in the buffer.
+    pub fn finish(mut self) -> Vec<StreamEvent> {
+        let events = parse_line_bytes(&self.buffer).into_iter().collect();
+        self.buffer.clear();
+        events
+    }
+}
+
+fn parse_line_bytes(line: &[u8]) -> Option<StreamEvent> {
+    parse_line(std::str::from_utf8(line).ok()?)
+}
+
+fn parse_line(line: &str) -> Option<StreamEvent> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    if trimmed.starts_with('#') {
+        return read_comment_line(trimmed)
+            .ok()
+            .map(|(_, comment)| StreamEvent::Comment(comment));
+    }
+    read_sample_line(trimmed)
+        .ok()
+        .map(|(_, sample)| StreamEvent::Sample(sample))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::comment::CommentType;
+    use crate::parser::label::LabelList;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_feed_buffers_partial_line() {
+        let mut stream = SampleStream::new();
+        assert_eq!(stream.feed(b"http_requests_total{method=\"po"), vec![]);
+        let events = stream.feed(b"st\"} 5\n");
+
+        let mut labels = HashMap::new();
+        labels.insert("method".to_string(), "post".to_string());
+
+        assert_eq!(
+            events,
+            vec![StreamEvent::Sample(SampleData::new(
+                "http_requests_total".into(),
+                LabelList::from_map(labels),
+                5.0,
+                None
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_feed_emits_multiple_lines_from_one_chunk() {
+        let mut stream = SampleStream::new();
+        let events = stream.feed(b"# TYPE metric counter\nmetric 1\nmetric 2\n");
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0],
+            StreamEvent::Comment(Comment::new(
+                "metric".into(),
+                CommentType::Type,
+                "counter".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_feed_buffers_line_split_mid_utf8_character() {
+        let mut stream = SampleStream::new();
+        // "café" encodes 'é' as the two bytes 0xC3 0xA9; split the chunk
+        // between them so neither half is valid UTF-8 on its own.
+        let line = "metric{name=\"café\"} 1\n".as_bytes().to_vec();
+        let split = line.iter().position(|&b| b == 0xC3).unwrap() + 1;
+
+        assert_eq!(stream.feed(&line[..split]), vec![]);
+        let events = stream.feed(&line[split..]);
+
+        let mut labels = HashMap::new();
+        labels.insert("name".to_string(), "café".to_string());
+
+        assert_eq!(
+            events,
+            vec![StreamEvent::Sample(SampleData::new(
+                "metric".into(),
+                LabelList::from_map(labels),
+                1.0,
+                None
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_finish_flushes_trailing_unterminated_line() {
+        let mut stream = SampleStream::new();
+        assert_eq!(stream.feed(b"metric 42"), vec![]);
+        assert_eq!(
+            stream.finish(),
+            vec![StreamEvent::Sample(SampleData::new(
+                "metric".into(),
+                LabelList::new(),
+                42.0,
+                None
+            ))]
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt;
+
+use crate::parser::comment::{Comment, CommentType};
+use crate::parser::family::{MetricFamily, MetricType};
+use crate::parser::label::LabelList;
+use crate::parser::metric_data::SampleData;
+
+/// Renders a value using the Prometheus text exposition format.
+pub trait ToExpositionString {
+    fn to_exposition_string(&self) -> String;
+}
+
+impl ToExpositionString for SampleData {
+    fn to_exposition_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToExpositionString for LabelList {
+    fn to_exposition_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToExpositionString for Comment {
+    fn to_exposition_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn metric_type_name(metric_type: MetricType) -> &'static str {
+    match metric_type {
+        MetricType::Counter => "counter",
+        MetricType::Gauge => "gauge",
+        MetricType::Histogram => "histogram",
+        MetricType::Summary => "summary",
+        MetricType::Untyped => "untyped",
+    }
+}
+
+impl fmt::Display for LabelList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        write!(f, "{{")?;
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}=\"{}\"", key, escape_label_value(value))?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl fmt::Display for SampleData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{} {}", self.name, self.labels, format_value(self.value))?;
+        if let Some(timestamp) = self.timestamp {
+            write!(f, " {}", timestamp)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let comment_type = match self.comment_type {
+            CommentType::Help => "HELP",
+            CommentType::Type => "TYPE",
+            CommentType::Unknown => "",
+        };
+        write!(f, "# {} {} {}", comment_type, self.metric, self.description)
+    }
+}
+
+/// Encodes a set of [`MetricFamily`] values back into an exposition
+/// document, writing the `# HELP`/`# TYPE` header comments before each
+/// family's samples.
+pub fn encode_exposition(families: &[MetricFamily]) -> String {
+    let mut out = String::new();
+    for family in families {
+        if !family.help.is_empty() {
+            out.push_str(&format!("# HELP {} {}\n", family.name, family.help));
+        }
+        out.push_str(&format!(
+            "# TYPE {} {}\n",
+            family.name,
+            metric_type_name(family.metric_type)
+        ));
+        for sample in &family.samples {
+            out.push_str(&sample.to_exposition_string());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::document::parse_exposition;
+    use crate::parser::line_parser::{read_comment, read_sample};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_sample_data_round_trips_through_display() {
+        let mut labels = HashMap::new();
+        labels.insert("method".to_string(), "post".to_string());
+        let sample = SampleData::new(
+            "http_requests_total".into(),
+            LabelList::from_map(labels),
+            1500.0,
+            Some(1395066363000),
+        );
+
+        let encoded = sample.to_exposition_string();
+        assert_eq!(read_sample(&encoded).unwrap(), sample);
+    }
+
+    #[test]
+    fn test_sample_data_round_trips_escaped_label_values() {
+        let mut labels = HashMap::new();
+        labels.insert(
+            "error".to_string(),
+            "Cannot find file:\n\"FILE.TXT\"".to_string(),
+        );
+        let sample = SampleData::new("msdos_error".into(), LabelList::from_map(labels), 1.0, None);
+
+        let encoded = sample.to_exposition_string();
+        assert_eq!(read_sample(&encoded).unwrap(), sample);
+    }
+
+    #[test]
+    fn test_sample_data_round_trips_infinity_and_nan() {
+        for value in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            let sample = SampleData::new("weird_metric".into(), LabelList::new(), value, None);
+            let encoded = sample.to_exposition_string();
+            let decoded = read_sample(&encoded).unwrap();
+            if value.is_nan() {
+                assert!(decoded.value.is_nan());
+            } else {
+                assert_eq!(decoded.value, value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_comment_round_trips_through_display() {
+        let comment = Comment::new(
+            "node_cpu_seconds_total".into(),
+            CommentType::Help,
+            "Seconds the CPUs spent in each mode.".into(),
+        );
+        let encoded = comment.to_exposition_string();
+        assert_eq!(read_comment(&encoded).unwrap(), comment);
+    }
+
+    #[test]
+    fn test_encode_exposition_round_trips_through_parse_exposition() {
+        let doc = "\
+# HELP http_requests_total The total number of HTTP requests.
+# TYPE http_requests_total counter
+http_requests_total{method=\"post\",code=\"200\"} 1027
+";
+        let families = parse_exposition(doc);
+        let encoded = encode_exposition(&families);
+        assert_eq!(parse_exposition(&encoded), families);
+    }
+}